@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::Error;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// One named Ghost Tunnel server a user can connect to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerProfile {
+    pub name: String,
+    pub endpoint: String,
+    pub public_key: String,
+    pub allowed_ips: Vec<String>,
+}
+
+/// Persisted app configuration: where to find the bridge, and the server
+/// profiles a user has set up. Loaded once at startup into `ConfigState`
+/// and rewritten to disk whenever `save_config`/`set_active_profile` run.
+///
+/// Field order matters here: TOML requires every scalar/`Option` key to
+/// come before the first array-of-tables, so `profiles` - the only field
+/// that serializes as `[[profiles]]` - must stay last. Reordering it
+/// above a scalar field produces a `ValueAfterTable` serialize error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub python_path: String,
+    pub bridge_path: String,
+    pub active_profile: Option<String>,
+    pub profiles: Vec<ServerProfile>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            python_path: "python3".to_string(),
+            bridge_path: "src/client/bridge.py".to_string(),
+            active_profile: None,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn active_profile(&self) -> Option<&ServerProfile> {
+        let name = self.active_profile.as_ref()?;
+        self.profiles.iter().find(|p| &p.name == name)
+    }
+}
+
+/// Managed state wrapping the loaded `Config` behind an `RwLock`, since
+/// commands read it far more often (every connect) than they write it.
+pub struct ConfigState(pub RwLock<Config>);
+
+fn config_path(app_handle: &AppHandle) -> Result<PathBuf, Error> {
+    app_handle
+        .path_resolver()
+        .app_config_dir()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+        .ok_or(Error::ConfigDirUnavailable)
+}
+
+/// Loads the config for app startup. Missing file or unparsable contents
+/// both fall back to `Config::default()` rather than failing the app
+/// launch - there's nothing a user can do about a bad config before the
+/// window even opens.
+pub fn load(app_handle: &AppHandle) -> Config {
+    let path = match config_path(app_handle) {
+        Ok(path) => path,
+        Err(_) => return Config::default(),
+    };
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write(app_handle: &AppHandle, config: &Config) -> Result<(), Error> {
+    let path = config_path(app_handle)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = toml::to_string_pretty(config)?;
+    fs::write(&path, contents)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_config(state: State<'_, ConfigState>) -> Config {
+    state.0.read().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn save_config(
+    config: Config,
+    state: State<'_, ConfigState>,
+    app_handle: AppHandle,
+) -> Result<(), Error> {
+    write(&app_handle, &config)?;
+    *state.0.write().unwrap() = config;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_active_profile(
+    name: String,
+    state: State<'_, ConfigState>,
+    app_handle: AppHandle,
+) -> Result<(), Error> {
+    let mut config = state.0.write().unwrap();
+    if !config.profiles.iter().any(|p| p.name == name) {
+        return Err(Error::UnknownProfile(name));
+    }
+    config.active_profile = Some(name);
+    write(&app_handle, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml_with_an_active_profile() {
+        let config = Config {
+            python_path: "python3".to_string(),
+            bridge_path: "src/client/bridge.py".to_string(),
+            active_profile: Some("home".to_string()),
+            profiles: vec![ServerProfile {
+                name: "home".to_string(),
+                endpoint: "vpn.example.com:51820".to_string(),
+                public_key: "abc123".to_string(),
+                allowed_ips: vec!["0.0.0.0/0".to_string()],
+            }],
+        };
+
+        let serialized = toml::to_string_pretty(&config).expect("config should serialize");
+        let deserialized: Config = toml::from_str(&serialized).expect("config should parse back");
+
+        assert_eq!(deserialized.active_profile, config.active_profile);
+        assert_eq!(deserialized.profiles.len(), 1);
+        assert_eq!(deserialized.active_profile().unwrap().name, "home");
+    }
+
+    #[test]
+    fn active_profile_is_none_when_none_is_set() {
+        let config = Config::default();
+        assert!(config.active_profile.is_none());
+        assert!(config.active_profile().is_none());
+    }
+
+    #[test]
+    fn active_profile_is_none_when_the_name_does_not_match_any_profile() {
+        let config = Config {
+            active_profile: Some("missing".to_string()),
+            profiles: vec![ServerProfile {
+                name: "home".to_string(),
+                endpoint: "vpn.example.com:51820".to_string(),
+                public_key: "abc123".to_string(),
+                allowed_ips: vec!["0.0.0.0/0".to_string()],
+            }],
+            ..Config::default()
+        };
+        assert!(config.active_profile().is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// All the ways a Ghost Tunnel command can fail, surfaced to the
+/// frontend as structured JSON (`{ kind, message }`) instead of an
+/// opaque string, so the UI can tell a spawn failure from a ZKP
+/// rejection from malformed bridge output and react accordingly.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to spawn the bridge process: {0}")]
+    BridgeSpawn(std::io::Error),
+
+    #[error("failed to read or write the bridge channel: {0}")]
+    BridgeChannelIo(std::io::Error),
+
+    #[error("timed out waiting for the bridge socket")]
+    BridgeChannelTimeout,
+
+    #[error("duplicating the bridge channel is not supported on this platform")]
+    BridgeChannelDuplicationUnsupported,
+
+    #[error("bridge channel is not connected")]
+    ChannelNotConnected,
+
+    #[error("failed to parse bridge output: {0}")]
+    BridgeOutputParse(#[from] serde_json::Error),
+
+    #[error("the bridge rejected the zero-knowledge proof")]
+    ZkpRejected,
+
+    #[error("the tunnel failed: {detail}")]
+    TunnelFailed { detail: String },
+
+    #[error("no active server profile configured")]
+    NoActiveProfile,
+
+    #[error("no server profile named '{0}'")]
+    UnknownProfile(String),
+
+    #[error("could not resolve the app config directory")]
+    ConfigDirUnavailable,
+
+    #[error("failed to read or write the config file: {0}")]
+    ConfigIo(#[from] std::io::Error),
+
+    #[error("failed to parse the config file: {0}")]
+    ConfigParse(#[from] toml::de::Error),
+
+    #[error("failed to serialize the config file: {0}")]
+    ConfigSerialize(#[from] toml::ser::Error),
+}
+
+impl Error {
+    /// A stable, camelCase tag the frontend can match on without parsing
+    /// `message`, which is free-form and not meant to be localized there.
+    fn kind(&self) -> &'static str {
+        match self {
+            Error::BridgeSpawn(_) => "bridgeSpawn",
+            Error::BridgeChannelIo(_) => "bridgeChannelIo",
+            Error::BridgeChannelTimeout => "bridgeChannelTimeout",
+            Error::BridgeChannelDuplicationUnsupported => "bridgeChannelDuplicationUnsupported",
+            Error::ChannelNotConnected => "channelNotConnected",
+            Error::BridgeOutputParse(_) => "bridgeOutputParse",
+            Error::ZkpRejected => "zkpRejected",
+            Error::TunnelFailed { .. } => "tunnelFailed",
+            Error::NoActiveProfile => "noActiveProfile",
+            Error::UnknownProfile(_) => "unknownProfile",
+            Error::ConfigDirUnavailable => "configDirUnavailable",
+            Error::ConfigIo(_) => "configIo",
+            Error::ConfigParse(_) => "configParse",
+            Error::ConfigSerialize(_) => "configSerialize",
+        }
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Error", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_as_a_kind_and_message_object() {
+        let value = serde_json::to_value(Error::ZkpRejected).expect("error should serialize");
+        assert_eq!(value["kind"], "zkpRejected");
+        assert_eq!(value["message"], Error::ZkpRejected.to_string());
+    }
+
+    #[test]
+    fn variant_with_data_carries_it_into_the_message() {
+        let error = Error::TunnelFailed {
+            detail: "peer unreachable".to_string(),
+        };
+        let value = serde_json::to_value(&error).expect("error should serialize");
+        assert_eq!(value["kind"], "tunnelFailed");
+        assert_eq!(value["message"], "the tunnel failed: peer unreachable");
+    }
+}
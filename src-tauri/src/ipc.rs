@@ -0,0 +1,209 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+use interprocess::local_socket::{LocalSocketStream, NameTypeSupport};
+use serde_json::Value;
+use shared_child::SharedChild;
+use tauri::{AppHandle, Manager};
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::state::{AppState, ConnectionStatus};
+
+const SOCKET_NAME: &str = "ghost-tunnel-bridge";
+
+/// Picks a namespaced socket name where the platform supports one
+/// (Linux abstract sockets, Windows named pipes) and falls back to a
+/// filesystem path where it doesn't (macOS).
+fn socket_name() -> String {
+    use NameTypeSupport::*;
+    match NameTypeSupport::query() {
+        OnlyPaths => format!("/tmp/{}.sock", SOCKET_NAME),
+        OnlyNamespaced | Both => format!("@{}", SOCKET_NAME),
+    }
+}
+
+/// Spawns the bridge once as a long-lived process and connects to the
+/// local socket/named pipe it serves, keeping the ZKP session warm
+/// across reconnects instead of cold-starting a fresh interpreter (and
+/// re-running ZKP setup) on every `toggle_vpn` call.
+///
+/// The open channel and the bridge's `SharedChild` are stored in the
+/// managed `AppState`; a background task forwards every line the bridge
+/// writes back as a `vpn://status` event.
+pub fn start_persistent_bridge(app_handle: &AppHandle, config: &Config) -> Result<(), Error> {
+    let name = socket_name();
+
+    let mut command = Command::new(&config.python_path);
+    command
+        .arg(&config.bridge_path)
+        .arg("serve")
+        .arg("--socket")
+        .arg(&name);
+    let child = SharedChild::spawn(&mut command).map_err(Error::BridgeSpawn)?;
+
+    let stream = match connect_with_retry(&name, Duration::from_secs(5)) {
+        Ok(stream) => stream,
+        Err(e) => {
+            kill_orphaned_child(child);
+            return Err(e);
+        }
+    };
+    let reader_stream = match clone_stream(&stream) {
+        Ok(reader_stream) => reader_stream,
+        Err(e) => {
+            kill_orphaned_child(child);
+            return Err(e);
+        }
+    };
+
+    let state = app_handle.state::<AppState>();
+    *state.child.lock().unwrap() = Some(child);
+    *state.socket.lock().unwrap() = Some(stream);
+    drop(state);
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        stream_bridge_events(app_handle, reader_stream).await;
+    });
+
+    Ok(())
+}
+
+/// Starts the bridge if it isn't already running - either because
+/// `setup()`'s initial attempt failed (bad `python_path`/`bridge_path`) or
+/// because a prior `start_persistent_bridge` call itself failed. Called
+/// from `toggle_vpn` so a config fix made after launch takes effect on
+/// the next connect without restarting the app.
+pub fn ensure_bridge_started(app_handle: &AppHandle, config: &Config) -> Result<(), Error> {
+    let already_running = app_handle.state::<AppState>().socket.lock().unwrap().is_some();
+    if already_running {
+        return Ok(());
+    }
+    start_persistent_bridge(app_handle, config)
+}
+
+/// `interprocess` 1.2's `LocalSocketStream` has no `try_clone` - unlike
+/// `std::os::unix::net::UnixStream`, it doesn't expose one. Splitting the
+/// one connected socket into an owned writer (kept in `AppState` for
+/// `send_frame`) and an owned reader (moved into the background
+/// `stream_bridge_events` task) means duplicating the underlying file
+/// descriptor ourselves; both ends read/write the same socket
+/// independently, so this is safe without any extra locking.
+#[cfg(unix)]
+fn clone_stream(stream: &LocalSocketStream) -> Result<LocalSocketStream, Error> {
+    let fd = unsafe { libc::dup(stream.as_raw_fd()) };
+    if fd < 0 {
+        return Err(Error::BridgeChannelIo(std::io::Error::last_os_error()));
+    }
+    Ok(unsafe { LocalSocketStream::from_raw_fd(fd) })
+}
+
+#[cfg(not(unix))]
+fn clone_stream(_stream: &LocalSocketStream) -> Result<LocalSocketStream, Error> {
+    Err(Error::BridgeChannelDuplicationUnsupported)
+}
+
+/// `SharedChild`'s `Drop` does not kill the process, so a bridge that
+/// spawned but never came up on its socket would otherwise be leaked as
+/// an orphan every time `start_persistent_bridge` bails out early.
+fn kill_orphaned_child(child: SharedChild) {
+    if let Err(e) = child.kill() {
+        eprintln!("Ghost Tunnel bridge kill failed while cleaning up a failed start: {}", e);
+    }
+}
+
+fn connect_with_retry(name: &str, timeout: Duration) -> Result<LocalSocketStream, Error> {
+    let deadline = Instant::now() + timeout;
+    let mut last_err = None;
+    while Instant::now() < deadline {
+        match LocalSocketStream::connect(name) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => {
+                last_err = Some(e);
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        }
+    }
+    Err(last_err
+        .map(Error::BridgeChannelIo)
+        .unwrap_or(Error::BridgeChannelTimeout))
+}
+
+/// Writes one line-delimited JSON command frame to the bridge. The reply
+/// and any further events (handshake progress, periodic stats) arrive
+/// asynchronously on the same channel and are forwarded to the frontend
+/// by `stream_bridge_events`.
+pub fn send_frame(state: &AppState, frame: Value) -> Result<(), Error> {
+    let mut guard = state.socket.lock().unwrap();
+    let stream = guard.as_mut().ok_or(Error::ChannelNotConnected)?;
+    writeln!(stream, "{}", frame).map_err(Error::BridgeChannelIo)
+}
+
+/// Convenience for command frames with no extra fields, e.g.
+/// `{"cmd":"disconnect"}`.
+pub fn send_command(state: &AppState, cmd: &str) -> Result<(), Error> {
+    send_frame(state, serde_json::json!({ "cmd": cmd }))
+}
+
+async fn stream_bridge_events(app_handle: AppHandle, stream: LocalSocketStream) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Ghost Tunnel bridge channel read failed: {}", e);
+                break;
+            }
+        }
+
+        let mut payload: Value = match serde_json::from_str(line.trim_end()).map_err(Error::from) {
+            Ok(payload) => payload,
+            Err(e) => {
+                eprintln!("Ghost Tunnel bridge sent malformed frame: {}", e);
+                continue;
+            }
+        };
+
+        if payload["event"] == "handshake_complete" {
+            let success = payload["success"].as_bool().unwrap_or(false);
+            if !success {
+                let error = match payload["detail"].as_str() {
+                    Some(detail) => Error::TunnelFailed {
+                        detail: detail.to_string(),
+                    },
+                    None => Error::ZkpRejected,
+                };
+                eprintln!("Ghost Tunnel handshake failed: {}", error);
+                // Carried as a typed `{ kind, message }` object (see
+                // `error::Error`'s `Serialize` impl) so the frontend can
+                // distinguish a ZKP rejection from a malformed-output or
+                // spawn failure the same way a failed command would.
+                if let Some(object) = payload.as_object_mut() {
+                    object.insert(
+                        "error".to_string(),
+                        serde_json::to_value(&error).unwrap_or(Value::Null),
+                    );
+                }
+            }
+            let state = app_handle.state::<AppState>();
+            *state.status.lock().unwrap() = if success {
+                ConnectionStatus::Connected
+            } else {
+                ConnectionStatus::Disconnected
+            };
+            drop(state);
+            crate::refresh_tray(&app_handle);
+        }
+
+        let _ = app_handle.emit_all("vpn://status", payload);
+    }
+}
@@ -1,47 +1,220 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::process::Command;
-use tauri::Manager;
-use serde_json::Value;
+mod config;
+mod error;
+mod ipc;
+mod state;
 
-#[tauri::command]
-async fn toggle_vpn(active: bool) -> Result<String, String> {
-    let python_cmd = "python3";
-    let bridge_path = "src/client/bridge.py";
+use std::sync::RwLock;
+use std::time::Instant;
+
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, RunEvent, State, SystemTray, SystemTrayEvent,
+    SystemTrayMenu, SystemTrayMenuItem, WindowEvent,
+};
+
+use config::ConfigState;
+use error::Error;
+use state::{AppState, ConnectionStatus};
 
+#[cfg(unix)]
+fn send_terminate(pid: u32) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+#[cfg(not(unix))]
+fn send_terminate(_pid: u32) {
+    // Windows has no SIGTERM equivalent for an arbitrary child; shutdown
+    // falls straight through to `.kill()` below.
+}
+
+/// Starts (or stops) the Ghost Tunnel by writing a command frame to the
+/// persistent bridge channel. The reply and any further events (handshake
+/// progress, periodic stats) arrive on `vpn://status`, which the frontend
+/// subscribes to with `listen(...)` and switches on the payload's `event`
+/// field:
+///
+/// - `zkp_proof_generated` - the handshake's proof step completed.
+/// - `handshake_complete` - `{ event, success, detail?, error? }`, the
+///   terminal outcome of a connect attempt. `error` is only present when
+///   `success` is `false` and is the same `{ kind, message }` shape a
+///   failed command returns (see `error::Error`).
+/// - `stats` - periodic `{ event, rx_bytes, tx_bytes, latency_ms }` while
+///   the tunnel is up.
+#[tauri::command]
+async fn toggle_vpn(
+    active: bool,
+    state: State<'_, AppState>,
+    config_state: State<'_, ConfigState>,
+    app_handle: AppHandle,
+) -> Result<String, Error> {
     if active {
-        println!("🔐 Executing ZKP Auth & Ghost Connect...");
-        
-        let output = Command::new(python_cmd)
-            .arg(bridge_path)
-            .arg("connect")
-            .output()
-            .map_err(|e| e.to_string())?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        println!("Bridge Output: {}", stdout);
-        
-        let v: Value = serde_json::from_str(&stdout).map_err(|e| e.to_string())?;
-        if v["success"].as_bool().unwrap_or(false) {
-            Ok("Connected".to_string())
-        } else {
-            Err("Authentication or connection failed".to_string())
-        }
+        let config = config_state.0.read().unwrap().clone();
+        let profile = config.active_profile().cloned().ok_or(Error::NoActiveProfile)?;
+
+        ipc::ensure_bridge_started(&app_handle, &config)?;
+
+        println!("🔐 Requesting ZKP Auth & Ghost Connect to '{}'...", profile.name);
+        *state.status.lock().unwrap() = ConnectionStatus::Connecting;
+        refresh_tray(&app_handle);
+        ipc::send_frame(
+            &state,
+            serde_json::json!({
+                "cmd": "connect",
+                "endpoint": profile.endpoint,
+                "public_key": profile.public_key,
+                "allowed_ips": profile.allowed_ips,
+            }),
+        )?;
+        Ok("Connecting".to_string())
     } else {
-        println!("🛑 Stopping Ghost Tunnel...");
-        Command::new(python_cmd)
-            .arg(bridge_path)
-            .arg("stop")
-            .output()
-            .map_err(|e| e.to_string())?;
-            
+        println!("🛑 Requesting Ghost Tunnel stop...");
+        ipc::send_command(&state, "disconnect")?;
+        *state.status.lock().unwrap() = ConnectionStatus::Disconnected;
+        refresh_tray(&app_handle);
         Ok("Disconnected".to_string())
     }
 }
 
+/// Reports the connection state as last reported by the bridge over
+/// `vpn://status`, so a reloaded UI can recover the real state instead
+/// of assuming `Disconnected`.
+#[tauri::command]
+fn connection_status(state: State<'_, AppState>) -> ConnectionStatus {
+    *state.status.lock().unwrap()
+}
+
+/// Terminates the persistent bridge process: signals it and falls back
+/// to a hard `.kill()` if it hasn't exited within
+/// `AppState::SHUTDOWN_GRACE`. Called once, on app exit.
+fn shutdown_bridge(state: &State<'_, AppState>) {
+    let child = match state.child.lock().unwrap().take() {
+        Some(child) => child,
+        None => return,
+    };
+
+    send_terminate(child.id());
+
+    let deadline = Instant::now() + AppState::SHUTDOWN_GRACE;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return,
+            Ok(None) if Instant::now() >= deadline => break,
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+            Err(e) => {
+                eprintln!("Ghost Tunnel bridge wait failed during shutdown: {}", e);
+                return;
+            }
+        }
+    }
+
+    if let Err(e) = child.kill() {
+        eprintln!("Ghost Tunnel bridge kill failed during shutdown: {}", e);
+    }
+}
+
+fn build_tray() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("connect", "Connect"))
+        .add_item(CustomMenuItem::new("disconnect", "Disconnect"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("show", "Show Window"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit", "Quit"));
+    SystemTray::new().with_menu(menu).with_title("Ghost Tunnel")
+}
+
+/// Reflects the current connection state in the tray title so it stays
+/// correct whether it changed via the window, the tray, or an async
+/// bridge event.
+pub(crate) fn refresh_tray(app: &AppHandle) {
+    let status = *app.state::<AppState>().status.lock().unwrap();
+    let title = match status {
+        ConnectionStatus::Connected => "Ghost Tunnel - Connected",
+        ConnectionStatus::Connecting => "Ghost Tunnel - Connecting...",
+        ConnectionStatus::Disconnected => "Ghost Tunnel - Disconnected",
+    };
+    let _ = app.tray_handle().set_title(title);
+}
+
+fn on_system_tray_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => show_main_window(app),
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            "connect" | "disconnect" => {
+                let app = app.clone();
+                let active = id == "connect";
+                tauri::async_runtime::spawn(async move {
+                    let _ = toggle_vpn(
+                        active,
+                        app.state::<AppState>(),
+                        app.state::<ConfigState>(),
+                        app.clone(),
+                    )
+                    .await;
+                });
+            }
+            "show" => show_main_window(app),
+            "quit" => app.exit(0),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![toggle_vpn])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .manage(AppState::default())
+        .setup(|app| {
+            let config = config::load(&app.handle());
+            // A failed spawn here - missing `python_path`, a stale
+            // `bridge_path` - is exactly the kind of thing the config UI
+            // this app ships exists to let a user fix. Don't abort launch
+            // over it; log it and let `toggle_vpn` retry the start lazily
+            // on the first connect attempt.
+            if let Err(e) = ipc::start_persistent_bridge(&app.handle(), &config) {
+                eprintln!("Ghost Tunnel bridge failed to start: {}", e);
+            }
+            app.manage(ConfigState(RwLock::new(config)));
+            Ok(())
+        })
+        .system_tray(build_tray())
+        .on_system_tray_event(on_system_tray_event)
+        .invoke_handler(tauri::generate_handler![
+            toggle_vpn,
+            connection_status,
+            config::get_config,
+            config::save_config,
+            config::set_active_profile,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| match event {
+            // Keep the Ghost Tunnel running in the background: closing the
+            // window just hides it, the tray Quit item is what actually exits.
+            RunEvent::WindowEvent {
+                event: WindowEvent::CloseRequested { api, .. },
+                ..
+            } => {
+                api.prevent_close();
+                hide_main_window(app_handle);
+            }
+            RunEvent::Exit => shutdown_bridge(&app_handle.state::<AppState>()),
+            _ => {}
+        });
+}
+
+fn hide_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.hide();
+    }
 }
@@ -0,0 +1,54 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use interprocess::local_socket::LocalSocketStream;
+use serde::Serialize;
+use shared_child::SharedChild;
+
+/// Connection lifecycle reported to the frontend.
+///
+/// Derived from the last event the bridge sent over `vpn://status`
+/// rather than from process liveness - the bridge is now a long-lived
+/// process serving many connects/disconnects, not one process per
+/// tunnel, so "is the child alive" no longer implies "is a tunnel up".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionStatus {
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+impl Default for ConnectionStatus {
+    fn default() -> Self {
+        ConnectionStatus::Disconnected
+    }
+}
+
+/// Tauri-managed state for the one persistent Ghost Tunnel bridge
+/// process this app keeps running for its whole lifetime.
+#[derive(Default)]
+pub struct AppState {
+    /// The long-lived bridge process, spawned once in `setup()`.
+    pub child: Mutex<Option<SharedChild>>,
+    /// The open request/response channel to that process.
+    pub socket: Mutex<Option<LocalSocketStream>>,
+    pub status: Mutex<ConnectionStatus>,
+}
+
+impl AppState {
+    /// How long bridge shutdown waits for a graceful exit after being
+    /// signalled, before it resorts to `.kill()`.
+    pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fresh_app_starts_disconnected() {
+        let state = AppState::default();
+        assert_eq!(*state.status.lock().unwrap(), ConnectionStatus::Disconnected);
+    }
+}